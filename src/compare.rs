@@ -0,0 +1,68 @@
+use std::cmp::Ordering;
+
+/// A comparator that imposes an ordering on values of type `T`.
+///
+/// A heap's comparator type parameter lets it be ordered by something other than `T`'s natural
+/// [`Ord`] implementation, without requiring callers to wrap their items (e.g. in
+/// [`std::cmp::Reverse`]). The heap always pops the item that sorts greatest under the
+/// comparator, so a comparator that reverses the natural order (as [`MinComparator`] does) yields
+/// a min-heap.
+///
+/// Heaps are built with the comparators in this module: [`BinomialHeap::new_by`] takes a closure
+/// and wraps it in a [`FnComparator`]. No constructor accepts any other implementation of this
+/// trait.
+///
+/// [`BinomialHeap::new_by`]: struct.BinomialHeap.html#method.new_by
+/// [`std::cmp::Reverse`]: https://doc.rust-lang.org/std/cmp/struct.Reverse.html
+pub trait Compare<T: ?Sized> {
+    /// Returns the ordering of `a` relative to `b`.
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// The default comparator for a [`BinomialHeap`](struct.BinomialHeap.html), ordering items by
+/// their natural [`Ord`] implementation and so producing a max-heap.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MaxComparator;
+
+impl<T: Ord> Compare<T> for MaxComparator {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// A comparator that reverses items' natural [`Ord`] implementation, turning a
+/// [`BinomialHeap`](struct.BinomialHeap.html) into a min-heap.
+///
+/// Built by [`BinomialHeap::new_min`](struct.BinomialHeap.html#method.new_min).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MinComparator;
+
+impl<T: Ord> Compare<T> for MinComparator {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+/// A comparator that delegates to a closure.
+///
+/// Built by [`BinomialHeap::new_by`](struct.BinomialHeap.html#method.new_by).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FnComparator<F>(pub(crate) F);
+
+impl<T, F: Fn(&T, &T) -> Ordering> Compare<T> for FnComparator<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a, b)
+    }
+}
+
+/// A comparator that orders items by a key extracted with a closure.
+///
+/// Returned by [`BinomialHeap::new_by_key`](struct.BinomialHeap.html#method.new_by_key).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct KeyComparator<F>(pub(crate) F);
+
+impl<T, K: Ord, F: Fn(&T) -> K> Compare<T> for KeyComparator<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a).cmp(&(self.0)(b))
+    }
+}
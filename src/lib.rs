@@ -9,10 +9,14 @@
 use std::fmt::{self, Debug};
 use std::marker::PhantomData;
 use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
 
+mod compare;
 mod node;
 
-pub use node::{IntoIter, Iter};
+pub use compare::{Compare, FnComparator, KeyComparator, MaxComparator, MinComparator};
+pub use node::{Handle, IntoIter, IntoIterSorted, Iter};
 
 /// A priority queue based on a binomial heap.
 ///
@@ -20,30 +24,84 @@ pub use node::{IntoIter, Iter};
 /// `BinaryHeap`, `BionmialHeap` provides an efficient `append` method, at the cost of greater
 /// memory usage, slower iteration, and poor cache locality.
 ///
+/// By default, a `BinomialHeap<T>` is a max-heap ordered by `T`'s natural [`Ord`] implementation.
+/// The second type parameter `C` selects a different ordering by implementing [`Compare<T>`]; use
+/// [`new_min`] for a min-heap, or [`new_by`] or [`new_by_key`] for an arbitrary custom comparator,
+/// without wrapping every item in [`std::cmp::Reverse`].
+///
 /// # Time Complexity
 ///
-/// | Operation                      | Time Complexity        |
-/// |--------------------------------|------------------------|
-/// | [`append`](#method.append)     | `O(log n)` (amortized) |
-/// | [`peek`](#method.peek)         | `O(log n)`             |
-/// | [`pop`](#method.pop)           | `O(log n)`             |
-/// | [`push`](#method.push)         | `O(1)` (amortized)     |
-/// | [`push_pop`](#method.push_pop) | `O(log n)`             |
-/// | [`replace`](#method.replace)   | `O(log n)`             |
+/// | Operation                                      | Time Complexity        |
+/// |-------------------------------------------------|------------------------|
+/// | [`append`](#method.append)                     | `O(log n)` (amortized) |
+/// | [`decrease_key`](#method.decrease_key)         | `O(log² n)`            |
+/// | [`From<Vec<T>>`](#method.from)                 | `O(n log n)`           |
+/// | [`increase_key`](#method.increase_key)         | `O(log n)`             |
+/// | [`into_sorted_vec`](#method.into_sorted_vec)   | `O(n log n)`           |
+/// | [`peek`](#method.peek)                         | `O(log n)`             |
+/// | [`peek_mut`](#method.peek_mut)                 | `O(log n)`             |
+/// | [`pop`](#method.pop)                           | `O(log n)`             |
+/// | [`push`](#method.push)                         | `O(1)` (amortized)     |
+/// | [`push_pop`](#method.push_pop)                 | `O(log n)`             |
+/// | [`replace`](#method.replace)                   | `O(log n)`             |
+/// | [`retain`](#method.retain)                     | `O(n)` (amortized)     |
+/// | [`update_key`](#method.update_key)             | `O(log² n)`            |
 ///
 /// [`BinaryHeap`]: https://doc.rust-lang.org/std/collections/struct.BinaryHeap.html
-#[derive(Clone)]
-pub struct BinomialHeap<T: Ord> {
+/// [`Compare<T>`]: trait.Compare.html
+/// [`new_min`]: #method.new_min
+/// [`new_by`]: #method.new_by
+/// [`new_by_key`]: #method.new_by_key
+/// [`std::cmp::Reverse`]: https://doc.rust-lang.org/std/cmp/struct.Reverse.html
+pub struct BinomialHeap<T, C = MaxComparator> {
     root: Option<Box<node::Node<T>>>,
     len: usize,
+    cmp: C,
+}
+
+// SAFETY: the `root` tree holds parent pointers (`node::Node::parent`) back into storage owned by
+// this same heap; nothing outside the heap ever aliases them. Sending or sharing a `BinomialHeap`
+// is therefore exactly as safe as sending or sharing its `T` items and its `C` comparator.
+unsafe impl<T: Send, C: Send> Send for BinomialHeap<T, C> {}
+unsafe impl<T: Sync, C: Sync> Sync for BinomialHeap<T, C> {}
+
+impl<T: Clone, C: Clone> Clone for BinomialHeap<T, C> {
+    fn clone(&self) -> Self {
+        let mut root = self.root.clone();
+
+        if let Some(ref mut root) = root {
+            node::fixup(root, None);
+        }
+
+        BinomialHeap { root: root, len: self.len, cmp: self.cmp.clone() }
+    }
 }
 
 impl<T: Ord> BinomialHeap<T> {
-    /// Returns a new heap.
+    /// Returns a new max-heap ordered by `T`'s natural [`Ord`] implementation.
     pub fn new() -> Self {
-        BinomialHeap { root: None, len: 0 }
+        BinomialHeap { root: None, len: 0, cmp: MaxComparator }
     }
 
+    /// Returns a new min-heap, ordered by the reverse of `T`'s natural [`Ord`] implementation.
+    pub fn new_min() -> BinomialHeap<T, MinComparator> {
+        BinomialHeap { root: None, len: 0, cmp: MinComparator }
+    }
+}
+
+impl<T> BinomialHeap<T> {
+    /// Returns a new heap ordered by `cmp`, such as `|a, b| b.cmp(a)` for a min-heap.
+    pub fn new_by<F: Fn(&T, &T) -> std::cmp::Ordering>(cmp: F) -> BinomialHeap<T, FnComparator<F>> {
+        BinomialHeap { root: None, len: 0, cmp: FnComparator(cmp) }
+    }
+
+    /// Returns a new heap ordered by comparing the keys that `f` extracts from its items.
+    pub fn new_by_key<K: Ord, F: Fn(&T) -> K>(f: F) -> BinomialHeap<T, KeyComparator<F>> {
+        BinomialHeap { root: None, len: 0, cmp: KeyComparator(f) }
+    }
+}
+
+impl<T, C> BinomialHeap<T, C> {
     /// Checks if the heap is empty.
     pub fn is_empty(&self) -> bool {
         self.root.is_none()
@@ -59,17 +117,142 @@ impl<T: Ord> BinomialHeap<T> {
         node::iter(&self.root, self.len)
     }
 
+    /// Removes all items from the heap.
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+
+    /// Removes all items from the heap and returns an iterator that yields them in arbitrary
+    /// order.
+    ///
+    /// All items are removed even if the iterator is not exhausted. However, the behavior of
+    /// this method is unspecified if the iterator is leaked (e.g. via [`mem::forget`]).
+    ///
+    /// [`mem::forget`]: https://doc.rust-lang.org/std/mem/fn.forget.html
+    pub fn drain(&mut self) -> Drain<T> {
+        let root = self.root.take();
+        let len = mem::replace(&mut self.len, 0);
+        Drain { iter: node::into_iter(root, len), marker: PhantomData }
+    }
+}
+
+impl<T, C: Compare<T>> BinomialHeap<T, C> {
     /// Returns a reference to the greatest item in the heap.
     ///
     /// Returns `None` if the heap is empty.
     pub fn peek(&self) -> Option<&T> {
-        node::peek(&self.root)
+        node::peek(&self.root, &self.cmp)
+    }
+
+    /// Returns a mutable reference to the greatest item in the heap, wrapped in a guard that
+    /// restores the heap's ordering invariant when it is dropped.
+    ///
+    /// Returns `None` if the heap is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binomial_heap::BinomialHeap;
+    ///
+    /// let mut heap = BinomialHeap::new();
+    /// assert!(heap.peek_mut().is_none());
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    ///
+    /// {
+    ///     let mut max = heap.peek_mut().unwrap();
+    ///     *max = 0;
+    /// }
+    ///
+    /// assert_eq!(heap.peek(), Some(&2));
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<PeekMut<T, C>> {
+        match node::peek_mut(&mut self.root, &self.cmp) {
+            None => None,
+            Some(node) => Some(PeekMut { heap: self, node: node, sift: false }),
+        }
     }
 
-    /// Pushes the given item onto the heap.
-    pub fn push(&mut self, item: T) {
-        node::push(&mut self.root, item);
+    /// Pushes the given item onto the heap, returning a [`Handle`] that can later be used with
+    /// [`update_key`], [`increase_key`], or [`decrease_key`] to update the item in place.
+    ///
+    /// The returned handle is invalidated by [`pop`], [`drain`], or [`clear`] of the item it
+    /// refers to, or by [`retain`] (which invalidates every outstanding handle, not just the one
+    /// for the removed item).
+    ///
+    /// [`Handle`]: struct.Handle.html
+    /// [`update_key`]: #method.update_key
+    /// [`increase_key`]: #method.increase_key
+    /// [`decrease_key`]: #method.decrease_key
+    /// [`pop`]: #method.pop
+    /// [`drain`]: #method.drain
+    /// [`clear`]: #method.clear
+    /// [`retain`]: #method.retain
+    pub fn push(&mut self, item: T) -> Handle<T> {
+        let handle = node::push(&mut self.root, item, &self.cmp);
         self.len += 1;
+        handle
+    }
+
+    /// Overwrites the item referenced by `handle` with `new`, restoring the heap's ordering
+    /// invariant by sifting the item up or down as needed.
+    ///
+    /// This is useful for algorithms, such as Dijkstra's, that need to adjust an item's priority
+    /// without the cost of a [`pop`] followed by a [`push`].
+    ///
+    /// [`pop`]: #method.pop
+    /// [`push`]: #method.push
+    ///
+    /// # Safety
+    ///
+    /// `handle` must have been returned by a call to [`push`] on this heap, and must not have
+    /// been invalidated by a subsequent [`pop`], [`drain`], or [`clear`] of the item it refers
+    /// to, or by a subsequent [`retain`].
+    ///
+    /// [`drain`]: #method.drain
+    /// [`clear`]: #method.clear
+    /// [`retain`]: #method.retain
+    pub unsafe fn update_key(&mut self, handle: &Handle<T>, new: T) {
+        unsafe { node::update_key(handle, new, &self.cmp) }
+    }
+
+    /// Increases the item referenced by `handle` to `new`.
+    ///
+    /// This is equivalent to [`update_key`], but documents the caller's intent and, in debug
+    /// builds, asserts that `new` is not less than the current item.
+    ///
+    /// [`update_key`]: #method.update_key
+    ///
+    /// # Safety
+    ///
+    /// See [`update_key`].
+    pub unsafe fn increase_key(&mut self, handle: &Handle<T>, new: T) {
+        debug_assert!(
+            self.cmp.compare(&new, unsafe { node::get(handle) }) != std::cmp::Ordering::Less,
+            "`new` must not be less than the current item",
+        );
+        unsafe { self.update_key(handle, new) }
+    }
+
+    /// Decreases the item referenced by `handle` to `new`.
+    ///
+    /// This is equivalent to [`update_key`], but documents the caller's intent and, in debug
+    /// builds, asserts that `new` is not greater than the current item.
+    ///
+    /// [`update_key`]: #method.update_key
+    ///
+    /// # Safety
+    ///
+    /// See [`update_key`].
+    pub unsafe fn decrease_key(&mut self, handle: &Handle<T>, new: T) {
+        debug_assert!(
+            self.cmp.compare(&new, unsafe { node::get(handle) }) != std::cmp::Ordering::Greater,
+            "`new` must not be greater than the current item",
+        );
+        unsafe { self.update_key(handle, new) }
     }
 
     /// Moves the given heap's items into the heap, leaving the given heap empty.
@@ -85,7 +268,7 @@ impl<T: Ord> BinomialHeap<T> {
         match self.root {
             None => mem::swap(self, other),
             Some(ref mut root) => {
-                node::append(root, other.root.take());
+                node::append(root, other.root.take(), &self.cmp);
                 self.len += mem::replace(&mut other.len, 0);
             }
         }
@@ -137,27 +320,54 @@ impl<T: Ord> BinomialHeap<T> {
     /// [`push_pop`]: #method.push_pop
     /// [`replace`]: #method.replace
     pub fn pop(&mut self) -> Option<T> {
-        node::pop(&mut self.root, &mut self.len)
+        node::pop(&mut self.root, &mut self.len, &self.cmp)
     }
 
-    /// Removes all items from the heap.
-    pub fn clear(&mut self) {
-        *self = Self::new();
+    /// Retains only the items for which `f` returns `true`, removing the rest.
+    ///
+    /// This drains the heap and rebuilds it from the items that pass `f`, so it invalidates every
+    /// outstanding [`Handle`] into the heap.
+    ///
+    /// [`Handle`]: struct.Handle.html
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let items: Vec<T> = self.drain().filter(|item| f(item)).collect();
+
+        for item in items {
+            self.push(item);
+        }
     }
 
-    /// Removes all items from the heap and returns an iterator that yields them in arbitrary
-    /// order.
+    /// Consumes the heap and returns a vector containing its items sorted in ascending order.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut vec: Vec<T> = self.into_iter_sorted().collect();
+        vec.reverse();
+        vec
+    }
+
+    /// Consumes the heap and returns an iterator that yields its items in descending order.
     ///
-    /// All items are removed even if the iterator is not exhausted. However, the behavior of
-    /// this method is unspecified if the iterator is leaked (e.g. via [`mem::forget`]).
+    /// Unlike [`into_iter`], which yields items in arbitrary order in `O(1)` per item, this
+    /// iterator yields items in `O(log n)` per item.
     ///
-    /// [`mem::forget`]: https://doc.rust-lang.org/std/mem/fn.forget.html
-    pub fn drain(&mut self) -> Drain<T> {
-        Drain { iter: mem::replace(self, Self::new()).into_iter(), marker: PhantomData }
+    /// [`into_iter`]: #method.into_iter
+    pub fn into_iter_sorted(self) -> IntoIterSorted<T, C> {
+        node::into_iter_sorted(self.root, self.len, self.cmp)
+    }
+
+    /// Returns an iterator that yields clones of the heap's items in descending order.
+    ///
+    /// This clones the whole heap up front and is equivalent to the following:
+    ///
+    /// ```
+    /// # let heap = binomial_heap::BinomialHeap::<i32>::new();
+    /// heap.clone().into_iter_sorted();
+    /// ```
+    pub fn iter_sorted(&self) -> IntoIterSorted<T, C> where T: Clone, C: Clone {
+        self.clone().into_iter_sorted()
     }
 }
 
-impl<T: Ord + Debug> Debug for BinomialHeap<T> {
+impl<T: Debug, C> Debug for BinomialHeap<T, C> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_list().entries(self).finish()
     }
@@ -169,7 +379,7 @@ impl<T: Ord> Default for BinomialHeap<T> {
     }
 }
 
-impl<T: Ord> Extend<T> for BinomialHeap<T> {
+impl<T, C: Compare<T>> Extend<T> for BinomialHeap<T, C> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
         for item in items { self.push(item); }
     }
@@ -183,7 +393,18 @@ impl<T: Ord> std::iter::FromIterator<T> for BinomialHeap<T> {
     }
 }
 
-impl<T: Ord> IntoIterator for BinomialHeap<T> {
+impl<T: Ord> From<Vec<T>> for BinomialHeap<T> {
+    /// Builds a heap from `items` by melding them pairwise, which is likely faster than pushing
+    /// them onto an empty heap one at a time.
+    fn from(items: Vec<T>) -> Self {
+        let cmp = MaxComparator;
+        let len = items.len();
+        let root = node::from_vec(items, &cmp);
+        BinomialHeap { root: root, len: len, cmp: cmp }
+    }
+}
+
+impl<T, C> IntoIterator for BinomialHeap<T, C> {
     type Item = T;
     type IntoIter = IntoIter<T>;
 
@@ -192,12 +413,12 @@ impl<T: Ord> IntoIterator for BinomialHeap<T> {
     }
 }
 
-impl<'a, T: Ord> IntoIterator for &'a BinomialHeap<T> {
+impl<'a, T, C> IntoIterator for &'a BinomialHeap<T, C> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
     fn into_iter(self) -> Iter<'a, T> {
-        self.iter()
+        node::iter(&self.root, self.len)
     }
 }
 
@@ -209,7 +430,7 @@ pub struct Drain<'a, T: 'a> {
     marker: PhantomData<&'a mut IntoIter<T>>,
 }
 
-impl<'a, T: Ord> Iterator for Drain<'a, T> {
+impl<'a, T> Iterator for Drain<'a, T> {
     type Item = T;
 
     fn next(&mut self) -> Option<T> {
@@ -221,12 +442,64 @@ impl<'a, T: Ord> Iterator for Drain<'a, T> {
     }
 }
 
-impl<'a, T: Ord> ExactSizeIterator for Drain<'a, T> {
+impl<'a, T> ExactSizeIterator for Drain<'a, T> {
     fn len(&self) -> usize {
         self.iter.len()
     }
 }
 
+/// A guard granting mutable access to the greatest item in a `BinomialHeap`.
+///
+/// Dropping this guard restores the heap's ordering invariant, sifting the item down among its
+/// children if it was accessed mutably. Use [`PeekMut::pop`] to remove the item instead, which
+/// skips the sift since the item is leaving the heap anyway.
+///
+/// Acquire through [`BinomialHeap::peek_mut`](struct.BinomialHeap.html#method.peek_mut).
+///
+/// [`PeekMut::pop`]: struct.PeekMut.html#method.pop
+pub struct PeekMut<'a, T: 'a, C: 'a + Compare<T>> {
+    heap: &'a mut BinomialHeap<T, C>,
+    node: NonNull<node::Node<T>>,
+    sift: bool,
+}
+
+impl<'a, T, C: Compare<T>> PeekMut<'a, T, C> {
+    /// Removes the peeked item from the heap and returns it.
+    pub fn pop(mut self) -> T {
+        self.sift = false;
+        unsafe { node::remove(&mut self.heap.root, &mut self.heap.len, self.node, &self.heap.cmp) }
+    }
+}
+
+impl<'a, T, C: Compare<T>> Drop for PeekMut<'a, T, C> {
+    fn drop(&mut self) {
+        if self.sift {
+            unsafe { node::sift_down(self.node, &self.heap.cmp) };
+        }
+    }
+}
+
+impl<'a, T, C: Compare<T>> Deref for PeekMut<'a, T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { node::item(&self.node) }
+    }
+}
+
+impl<'a, T, C: Compare<T>> DerefMut for PeekMut<'a, T, C> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.sift = true;
+        unsafe { node::item_mut(&mut self.node) }
+    }
+}
+
+impl<'a, T: Debug, C: Compare<T>> Debug for PeekMut<'a, T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PeekMut").field("item", &**self).finish()
+    }
+}
+
 #[allow(dead_code)]
 fn assert_covariance() {
     fn heap<'a, T: Ord>(heap: BinomialHeap<&'static T>) -> BinomialHeap<&'a T> {
@@ -241,3 +514,169 @@ fn assert_covariance() {
         iter
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node;
+
+    #[test]
+    fn into_sorted_vec_yields_ascending_order() {
+        let heap: BinomialHeap<i32> = vec![5, 1, 4, 2, 3].into_iter().collect();
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn iter_sorted_yields_descending_order_and_leaves_the_heap_untouched() {
+        let mut heap: BinomialHeap<i32> = vec![5, 1, 4, 2, 3].into_iter().collect();
+
+        let sorted: Vec<i32> = heap.iter_sorted().collect();
+        assert_eq!(sorted, vec![5, 4, 3, 2, 1]);
+
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3, 4, 5]);
+    }
+
+    // Each item carries its own id alongside the mutable priority so that, after a batch of
+    // `update_key` calls causes several nodes to swap items while sifting, each handle's `id` can
+    // be checked against the id it was pushed with. If `swap_items`, the `parent` traversal in
+    // `sift_up`, or the child scan in `sift_down` ever swapped an item without its handle (or vice
+    // versa), a handle would end up pointing at another item's id and this would catch it.
+    #[test]
+    fn update_key_maintains_handle_identity_across_levels() {
+        let mut heap = BinomialHeap::new_by_key(|item: &(i32, usize)| item.0);
+
+        // 16 items is enough to span several tree levels (orders 0 through 4).
+        let handles: Vec<_> = (0..16).map(|id| heap.push((id as i32, id))).collect();
+
+        unsafe {
+            // Sifts the new max up three levels, from just above a leaf to the root.
+            heap.increase_key(&handles[1], (100, 1));
+            // The previous call moved item 15 one level below the root; sifts it down to a leaf.
+            heap.decrease_key(&handles[15], (-1, 15));
+            // Sifts a leaf item up partway, stopping below the root.
+            heap.increase_key(&handles[4], (50, 4));
+            // Decreases a leaf item, which has no children and so stays where it is.
+            heap.decrease_key(&handles[8], (-2, 8));
+        }
+
+        for (id, handle) in handles.iter().enumerate() {
+            unsafe {
+                assert_eq!(node::get(handle).1, id, "handle {} lost track of its item", id);
+            }
+        }
+
+        let popped: Vec<_> = heap.into_sorted_vec().into_iter().rev().collect();
+        assert_eq!(popped[0], (100, 1));
+        assert_eq!(popped[1], (50, 4));
+        assert_eq!(popped[popped.len() - 1], (-2, 8));
+        assert!(popped.windows(2).all(|w| w[0].0 >= w[1].0));
+    }
+
+    #[test]
+    fn update_key_on_leaf_with_no_change_in_order_is_a_no_op() {
+        let mut heap = BinomialHeap::new();
+        let handles: Vec<_> = (0..8).map(|i| heap.push(i)).collect();
+
+        unsafe {
+            heap.update_key(&handles[0], 0);
+        }
+
+        assert_eq!(heap.into_sorted_vec(), (0..8).collect::<Vec<_>>());
+    }
+
+    // `PeekMut::pop` takes the `node::remove` path (splicing the node's children back into the
+    // root list via `finish_remove`) rather than `sift_down`, across a heap wide enough to span
+    // several binomial trees, to make sure that splice is wired up correctly.
+    #[test]
+    fn peek_mut_pop_splices_children_back_into_the_root_list() {
+        let mut heap: BinomialHeap<i32> = (0..32).collect();
+
+        for expected in (0..32).rev() {
+            assert_eq!(heap.peek_mut().unwrap().pop(), expected);
+        }
+
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn peek_mut_deref_mut_increases_the_top_item() {
+        let mut heap = BinomialHeap::new();
+        heap.push(1);
+        heap.push(3);
+        heap.push(2);
+
+        {
+            let mut max = heap.peek_mut().unwrap();
+            assert_eq!(*max, 3);
+            *max = 10;
+        }
+
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 10]);
+    }
+
+    #[test]
+    fn new_by_builds_a_min_heap() {
+        let mut heap = BinomialHeap::new_by(|a: &i32, b: &i32| b.cmp(a));
+        heap.extend(vec![5, 3, 8, 1, 9, 2]);
+
+        let mut popped = Vec::new();
+        while let Some(item) = heap.pop() {
+            popped.push(item);
+        }
+
+        assert_eq!(popped, vec![1, 2, 3, 5, 8, 9]);
+    }
+
+    #[test]
+    fn new_min_builds_a_min_heap() {
+        let mut heap = BinomialHeap::new_min();
+        heap.extend(vec![5, 3, 8, 1, 9, 2]);
+
+        let mut popped = Vec::new();
+        while let Some(item) = heap.pop() {
+            popped.push(item);
+        }
+
+        assert_eq!(popped, vec![1, 2, 3, 5, 8, 9]);
+    }
+
+    #[test]
+    fn new_by_key_orders_by_extracted_key() {
+        let mut heap = BinomialHeap::new_by_key(|item: &(i32, &str)| item.0);
+        heap.push((3, "c"));
+        heap.push((1, "a"));
+        heap.push((2, "b"));
+
+        assert_eq!(heap.pop(), Some((3, "c")));
+        assert_eq!(heap.pop(), Some((2, "b")));
+        assert_eq!(heap.pop(), Some((1, "a")));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn from_vec_matches_a_heap_built_by_pushing_one_at_a_time() {
+        // Covers the empty, singleton, and leftover-unpaired-element cases of the pairwise merge
+        // in `node::from_vec`, as well as a size large enough to span several tree orders.
+        for len in [0i32, 1, 2, 3, 5, 13, 100] {
+            let items: Vec<i32> = (0..len).map(|i| (i * 7) % (len.max(1))).collect();
+
+            let from_vec = BinomialHeap::from(items.clone());
+
+            let mut pushed = BinomialHeap::new();
+            pushed.extend(items);
+
+            assert_eq!(from_vec.into_sorted_vec(), pushed.into_sorted_vec(), "len = {}", len);
+        }
+    }
+
+    #[test]
+    fn retain_drains_filters_and_rebuilds_the_heap() {
+        let mut heap: BinomialHeap<i32> = (0..10).collect();
+
+        heap.retain(|&item| item % 2 == 0);
+
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.into_sorted_vec(), vec![0, 2, 4, 6, 8]);
+    }
+}
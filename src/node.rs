@@ -1,4 +1,8 @@
+use std::cmp::Ordering;
 use std::mem;
+use std::ptr::NonNull;
+
+use super::Compare;
 
 #[derive(Clone)]
 pub struct Node<T> {
@@ -6,30 +10,242 @@ pub struct Node<T> {
     order: usize,
     next: Option<Box<Node<T>>>,
     child: Option<Box<Node<T>>>,
+    parent: Option<NonNull<Node<T>>>,
+    handle: Box<HandleCell<T>>,
+}
+
+/// The out-of-line cell a [`Handle`](struct.Handle.html) points to.
+///
+/// Keeping this indirection separate from `Node` means that sifting a node up or down (which
+/// swaps `item`s between nodes rather than moving the nodes themselves) can keep each handle
+/// pointed at the item it was created for: the cell moves with the item, and `node` is updated to
+/// match.
+#[derive(Clone)]
+struct HandleCell<T> {
+    node: NonNull<Node<T>>,
 }
 
-pub fn append<T: Ord>(root: &mut Box<Node<T>>, other: Option<Box<Node<T>>>) {
+/// A stable reference to an item previously pushed onto a `BinomialHeap`, used to look up or
+/// update that item without a full [`pop`](struct.BinomialHeap.html#method.pop).
+///
+/// Acquire through [`BinomialHeap::push`](struct.BinomialHeap.html#method.push).
+///
+/// A handle is invalidated when the item it refers to is removed from the heap, whether by
+/// [`pop`], [`drain`], [`clear`], or [`retain`] (which invalidates every outstanding handle, not
+/// just the one for the removed item). Using a handle after it has been invalidated is undefined
+/// behavior.
+///
+/// [`pop`]: struct.BinomialHeap.html#method.pop
+/// [`drain`]: struct.BinomialHeap.html#method.drain
+/// [`clear`]: struct.BinomialHeap.html#method.clear
+/// [`retain`]: struct.BinomialHeap.html#method.retain
+pub struct Handle<T> {
+    cell: NonNull<HandleCell<T>>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+// SAFETY: `cell` is a self-contained pointer into storage owned by the `BinomialHeap` that
+// produced this handle; it is never aliased by anything outside that heap. Sending or sharing the
+// handle is therefore exactly as safe as sending or sharing the `T` it ultimately points to.
+unsafe impl<T: Send> Send for Handle<T> {}
+unsafe impl<T: Sync> Sync for Handle<T> {}
+
+pub fn append<T, C: Compare<T>>(root: &mut Box<Node<T>>, other: Option<Box<Node<T>>>, cmp: &C) {
     if let Some(other) = other {
         merge(root, other);
-        coalesce(root);
+        coalesce(root, cmp);
     }
 }
 
-pub fn push<T: Ord>(root: &mut Option<Box<Node<T>>>, item: T) {
-    let node = Some(Box::new(Node { item: item, order: 0, next: None, child: None }));
+/// Fixes up `parent` links and `handle` self-pointers throughout the subtree rooted at `node`,
+/// which must have just been relocated (e.g. by `Node::clone`).
+pub fn fixup<T>(node: &mut Box<Node<T>>, parent: Option<NonNull<Node<T>>>) {
+    node.parent = parent;
+
+    let node_ptr = NonNull::from(&mut **node);
+    node.handle.node = node_ptr;
+
+    if let Some(ref mut next) = node.next {
+        fixup(next, parent);
+    }
+
+    if let Some(ref mut child) = node.child {
+        fixup(child, Some(node_ptr));
+    }
+}
+
+/// Returns a new order-0 node holding `item`, with no parent, children, or siblings.
+fn singleton<T>(item: T) -> Box<Node<T>> {
+    let mut node = Box::new(Node {
+        item: item,
+        order: 0,
+        next: None,
+        child: None,
+        parent: None,
+        handle: Box::new(HandleCell { node: NonNull::dangling() }),
+    });
+
+    let node_ptr = NonNull::from(&mut *node);
+    node.handle.node = node_ptr;
+    node
+}
+
+pub fn push<T, C: Compare<T>>(root: &mut Option<Box<Node<T>>>, item: T, cmp: &C) -> Handle<T> {
+    let mut node = singleton(item);
+    let handle = Handle { cell: NonNull::from(&mut *node.handle) };
 
     match *root {
-        None => *root = node,
-        Some(ref mut root) => append(root, node),
+        None => *root = Some(node),
+        Some(ref mut root) => append(root, Some(node), cmp),
+    }
+
+    handle
+}
+
+/// Builds a sibling list from `items` by repeatedly melding pairs of trees together, halving the
+/// number of trees each pass, rather than appending the items one at a time onto a single
+/// accumulator. Each pass only ever touches independent, similarly sized pairs, which is friendlier
+/// to the cache than a long sequential fold that keeps dragging the same growing accumulator
+/// through every append.
+pub fn from_vec<T, C: Compare<T>>(items: Vec<T>, cmp: &C) -> Option<Box<Node<T>>> {
+    let mut trees: Vec<Box<Node<T>>> = items.into_iter().map(singleton).collect();
+
+    while trees.len() > 1 {
+        let mut melded = Vec::with_capacity((trees.len() + 1) / 2);
+        let mut pairs = trees.into_iter();
+
+        while let Some(mut a) = pairs.next() {
+            if let Some(b) = pairs.next() {
+                append(&mut a, Some(b), cmp);
+            }
+
+            melded.push(a);
+        }
+
+        trees = melded;
     }
+
+    trees.pop()
+}
+
+/// Returns a reference to the item that `handle` refers to.
+///
+/// # Safety
+///
+/// See [`Handle`](struct.Handle.html).
+pub unsafe fn get<T>(handle: &Handle<T>) -> &T {
+    unsafe { &handle.cell.as_ref().node.as_ref().item }
 }
 
-pub fn peek<T: Ord>(root: &Option<Box<Node<T>>>) -> Option<&T> {
+/// Overwrites the item that `handle` refers to with `new`, then sifts it up or down as needed to
+/// restore heap order.
+///
+/// # Safety
+///
+/// See [`Handle`](struct.Handle.html).
+pub unsafe fn update_key<T, C: Compare<T>>(handle: &Handle<T>, new: T, cmp: &C) {
+    unsafe {
+        let mut node = handle.cell.as_ref().node;
+        let increased = cmp.compare(&new, &node.as_ref().item) == Ordering::Greater;
+        node.as_mut().item = new;
+
+        if increased {
+            sift_up(node, cmp);
+        } else {
+            sift_down(node, cmp);
+        }
+    }
+}
+
+/// Swaps the items held by `a` and `b`, and the handles that point to them, so that each handle
+/// keeps referring to the same item even though it now lives in the other node.
+unsafe fn swap_items<T>(mut a: NonNull<Node<T>>, mut b: NonNull<Node<T>>) {
+    unsafe {
+        mem::swap(&mut a.as_mut().item, &mut b.as_mut().item);
+        mem::swap(&mut a.as_mut().handle, &mut b.as_mut().handle);
+        a.as_mut().handle.node = a;
+        b.as_mut().handle.node = b;
+    }
+}
+
+/// Sifts the item at `node` up toward the root of its binomial tree until its parent's item is no
+/// smaller, for use after the item has been increased.
+unsafe fn sift_up<T, C: Compare<T>>(mut node: NonNull<Node<T>>, cmp: &C) {
+    unsafe {
+        while let Some(parent) = node.as_ref().parent {
+            if cmp.compare(&parent.as_ref().item, &node.as_ref().item) != Ordering::Less {
+                return;
+            }
+
+            swap_items(parent, node);
+            node = parent;
+        }
+    }
+}
+
+/// Sifts the item at `node` down among its children until none is greater, for use after the item
+/// has been decreased.
+///
+/// Unlike `sift_up`, which only walks a single parent chain, this scans every child at each level
+/// it descends, so a node of order `k` costs `O(k)` per level; descending through orders
+/// `k, k - 1, ..., 0` costs `O(k^2) = O(log^2 n)` overall.
+pub unsafe fn sift_down<T, C: Compare<T>>(mut node: NonNull<Node<T>>, cmp: &C) {
+    unsafe {
+        loop {
+            let mut max_child: Option<NonNull<Node<T>>> = None;
+            let mut child = node.as_ref().child.as_ref().map(|child| NonNull::from(&**child));
+
+            while let Some(c) = child {
+                if max_child.map_or(true, |m| {
+                    cmp.compare(&c.as_ref().item, &m.as_ref().item) == Ordering::Greater
+                }) {
+                    max_child = Some(c);
+                }
+
+                child = c.as_ref().next.as_ref().map(|next| NonNull::from(&**next));
+            }
+
+            match max_child {
+                Some(max_child)
+                    if cmp.compare(&max_child.as_ref().item, &node.as_ref().item)
+                        == Ordering::Greater =>
+                {
+                    swap_items(node, max_child);
+                    node = max_child;
+                }
+                _ => return,
+            }
+        }
+    }
+}
+
+/// Clears the `parent` link of every node in the sibling list rooted at `node`, for use when that
+/// list's former parent has been removed and its members have become tree roots in their own
+/// right.
+fn clear_parent<T>(mut node: &mut Box<Node<T>>) {
+    loop {
+        node.parent = None;
+
+        match node.next {
+            None => return,
+            Some(ref mut next) => node = next,
+        }
+    }
+}
+
+pub fn peek<'a, T, C: Compare<T>>(root: &'a Option<Box<Node<T>>>, cmp: &C) -> Option<&'a T> {
     root.as_ref().map(|mut max| {
         let mut a = &max.next;
 
         while let Some(ref b) = *a {
-            if b.item > max.item { max = b; }
+            if cmp.compare(&b.item, &max.item) == Ordering::Greater { max = b; }
             a = &b.next;
         }
 
@@ -37,21 +253,111 @@ pub fn peek<T: Ord>(root: &Option<Box<Node<T>>>) -> Option<&T> {
     })
 }
 
-pub fn pop<T: Ord>(root: &mut Option<Box<Node<T>>>, len: &mut usize) -> Option<T> {
-    remove_max(root).map(|max| {
-        let max = *max;
-        let Node { item, child, order: _order, next: _next } = max;
-
-        match *root {
-            None => *root = child,
-            Some(ref mut root) => append(root, child),
+/// Returns a pointer to the node holding the greatest item in the sibling list rooted at `root`.
+///
+/// Walks the root list the same way `peek` does, but keeps a raw pointer instead of a reference
+/// so the node can later be mutated through [`item_mut`] and re-sifted by [`sift_down`].
+pub fn peek_mut<T, C: Compare<T>>(root: &mut Option<Box<Node<T>>>, cmp: &C) -> Option<NonNull<Node<T>>> {
+    root.as_ref().map(|root| {
+        let mut max = NonNull::from(&**root);
+        let mut a = root.next.as_ref().map(|next| NonNull::from(&**next));
+
+        while let Some(b) = a {
+            unsafe {
+                if cmp.compare(&b.as_ref().item, &max.as_ref().item) == Ordering::Greater {
+                    max = b;
+                }
+                a = b.as_ref().next.as_ref().map(|next| NonNull::from(&**next));
+            }
         }
 
-        *len -= 1;
-        item
+        max
     })
 }
 
+/// Returns a reference to the item held by `node`.
+///
+/// # Safety
+///
+/// `node` must point to a live node.
+pub unsafe fn item<'a, T>(node: &'a NonNull<Node<T>>) -> &'a T {
+    unsafe { &node.as_ref().item }
+}
+
+/// Returns a mutable reference to the item held by `node`.
+///
+/// # Safety
+///
+/// `node` must point to a live node.
+pub unsafe fn item_mut<'a, T>(node: &'a mut NonNull<Node<T>>) -> &'a mut T {
+    unsafe { &mut node.as_mut().item }
+}
+
+pub fn pop<T, C: Compare<T>>(root: &mut Option<Box<Node<T>>>, len: &mut usize, cmp: &C) -> Option<T> {
+    remove_max(root, cmp).map(|max| finish_remove(root, len, max, cmp))
+}
+
+/// Removes the node pointed to by `target` from the sibling list rooted at `root`, wherever it
+/// lies in that list, and returns the item it held.
+///
+/// # Safety
+///
+/// `target` must point to a node currently in the sibling list rooted at `root`.
+pub unsafe fn remove<T, C: Compare<T>>(
+    root: &mut Option<Box<Node<T>>>,
+    len: &mut usize,
+    target: NonNull<Node<T>>,
+    cmp: &C,
+) -> T {
+    let node = remove_node(root, target);
+    finish_remove(root, len, node, cmp)
+}
+
+/// Splices `node`'s children into the sibling list rooted at `root` and returns `node`'s item.
+///
+/// Shared by `pop` and `remove`, which differ only in how they locate the node to remove.
+fn finish_remove<T, C: Compare<T>>(
+    root: &mut Option<Box<Node<T>>>,
+    len: &mut usize,
+    node: Box<Node<T>>,
+    cmp: &C,
+) -> T {
+    let Node { item, mut child, .. } = *node;
+
+    if let Some(ref mut child) = child {
+        clear_parent(child);
+    }
+
+    match *root {
+        None => *root = child,
+        Some(ref mut root) => append(root, child, cmp),
+    }
+
+    *len -= 1;
+    item
+}
+
+/// Detaches the node pointed to by `target` from the sibling list rooted at `root`.
+fn remove_node<T>(root: &mut Option<Box<Node<T>>>, target: NonNull<Node<T>>) -> Box<Node<T>> {
+    if NonNull::from(&**root.as_ref().unwrap()) == target {
+        let mut node = root.take().unwrap();
+        *root = node.next.take();
+        return node;
+    }
+
+    let mut node = root.as_mut().unwrap();
+
+    loop {
+        if NonNull::from(&**node.next.as_ref().unwrap()) == target {
+            let mut next = node.next.take().unwrap();
+            node.next = next.next.take();
+            return next;
+        }
+
+        node = node.next.as_mut().unwrap();
+    }
+}
+
 pub fn iter<T>(root: &Option<Box<Node<T>>>, len: usize) -> Iter<T> {
     debug_assert!(root.is_some() ^ (len == 0));
     Iter { nodes: root.as_ref().map(|root| &**root).into_iter().collect(), len: len }
@@ -117,7 +423,7 @@ impl<T> Iterator for IntoIter<T> {
             self.len -= 1;
 
             let node = *node;
-            let Node { item, next, child, order: _order } = node;
+            let Node { item, next, child, .. } = node;
 
             if let Some(next) = next { self.nodes.push(next); }
             if let Some(child) = child { self.nodes.push(child); }
@@ -137,12 +443,51 @@ impl<T> ExactSizeIterator for IntoIter<T> {
     }
 }
 
+pub fn into_iter_sorted<T, C: Compare<T>>(
+    root: Option<Box<Node<T>>>,
+    len: usize,
+    cmp: C,
+) -> IntoIterSorted<T, C> {
+    debug_assert!(root.is_some() ^ (len == 0));
+    IntoIterSorted { root: root, len: len, cmp: cmp }
+}
+
+/// An iterator that yields the items in a `BinomialHeap` in descending order.
+///
+/// Acquire through [`BinomialHeap::into_iter_sorted`] or [`BinomialHeap::iter_sorted`].
+///
+/// [`BinomialHeap::into_iter_sorted`]: struct.BinomialHeap.html#method.into_iter_sorted
+/// [`BinomialHeap::iter_sorted`]: struct.BinomialHeap.html#method.iter_sorted
+pub struct IntoIterSorted<T, C: Compare<T>> {
+    root: Option<Box<Node<T>>>,
+    len: usize,
+    cmp: C,
+}
+
+impl<T, C: Compare<T>> Iterator for IntoIterSorted<T, C> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        pop(&mut self.root, &mut self.len, &self.cmp)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, C: Compare<T>> ExactSizeIterator for IntoIterSorted<T, C> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
 /// Merges the sibling list rooted at `b` into the sibling list rooted at `a` such that the
 /// resulting list is monotonically increasing by order.
 ///
 /// The lists rooted at `a` and `b` must be monotonically increasing by order.
 ///
-/// This method should always be followed by `coalesce(a)`.
+/// This method should always be followed by `coalesce(a, cmp)`.
 fn merge<T>(mut a: &mut Box<Node<T>>, mut b: Box<Node<T>>) {
     loop {
         let a_ = a;
@@ -159,11 +504,12 @@ fn merge<T>(mut a: &mut Box<Node<T>>, mut b: Box<Node<T>>) {
 }
 
 /// Makes `b` a child of `a`.
-fn link<T: Ord>(a: &mut Node<T>, mut b: Box<Node<T>>) {
+fn link<T, C: Compare<T>>(a: &mut Node<T>, mut b: Box<Node<T>>, cmp: &C) {
     debug_assert!(a.order == b.order);
     debug_assert!(b.next.is_none());
-    debug_assert!(a.item >= b.item);
+    debug_assert!(cmp.compare(&a.item, &b.item) != Ordering::Less);
 
+    b.parent = Some(NonNull::from(&mut *a));
     b.next = a.child.take();
     a.child = Some(b);
     a.order += 1;
@@ -176,7 +522,7 @@ fn link<T: Ord>(a: &mut Node<T>, mut b: Box<Node<T>>) {
 /// be valid max-heaps.
 ///
 /// This method should always be preceded by `merge`.
-fn coalesce<T: Ord>(mut a: &mut Box<Node<T>>) {
+fn coalesce<T, C: Compare<T>>(mut a: &mut Box<Node<T>>, cmp: &C) {
     enum Case {
         A,
         B,
@@ -191,7 +537,7 @@ fn coalesce<T: Ord>(mut a: &mut Box<Node<T>>) {
             Some(ref b) =>
                 if a_.order != b.order || b.next.as_ref().map_or(false, |c| c.order == b.order) {
                     Case::A
-                } else if a_.item >= b.item {
+                } else if cmp.compare(&a_.item, &b.item) != Ordering::Less {
                     Case::B
                 } else {
                     Case::C
@@ -203,7 +549,7 @@ fn coalesce<T: Ord>(mut a: &mut Box<Node<T>>) {
             Case::B => {
                 let mut b = a_.next.take().unwrap();
                 a_.next = b.next.take();
-                link(a_, b);
+                link(a_, b, cmp);
 
                 match a_.next {
                     None => return,
@@ -213,7 +559,7 @@ fn coalesce<T: Ord>(mut a: &mut Box<Node<T>>) {
             Case::C => {
                 let mut b = a_.next.take().unwrap();
                 mem::swap(a_, &mut b);
-                link(a_, b);
+                link(a_, b, cmp);
                 a = a_;
             }
         }
@@ -221,7 +567,7 @@ fn coalesce<T: Ord>(mut a: &mut Box<Node<T>>) {
 }
 
 /// Removes and returns the node with the maximum item from the sibling list rooted at `a`.
-fn remove_max<T: Ord>(mut a: &mut Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+fn remove_max<T, C: Compare<T>>(mut a: &mut Option<Box<Node<T>>>, cmp: &C) -> Option<Box<Node<T>>> {
     a.take().map(|mut max| {
         *a = max.next.take();
 
@@ -231,7 +577,7 @@ fn remove_max<T: Ord>(mut a: &mut Option<Box<Node<T>>>) -> Option<Box<Node<T>>>
             match *a_ {
                 None => return max,
                 Some(ref mut b) => {
-                    if b.item > max.item {
+                    if cmp.compare(&b.item, &max.item) == Ordering::Greater {
                         max.next = b.next.take();
                         mem::swap(&mut max, b);
                     }